@@ -0,0 +1,70 @@
+//! `#[derive(FromRow)]`, implementing [`tiberius::FromRow`] for a struct by
+//! reading each named field from the result set's column of the same name.
+//!
+//! Split into its own crate because a `proc-macro = true` crate can only
+//! export macros; the `FromRow` trait itself lives in `tiberius`, and this
+//! crate's macro is re-exported from there under the same name so callers
+//! only ever need `use tiberius::FromRow;`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Derives [`tiberius::FromRow`] for a struct with named fields, reading
+/// each field from the row's column of the same name.
+///
+/// ```ignore
+/// #[derive(tiberius::FromRow)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// let user: User = row_stream.into_typed::<User>().try_next().await?.unwrap();
+/// ```
+///
+/// Expands to an implementation that returns `tiberius::Error::Conversion`
+/// at runtime if a named column is missing from the result set, or if its
+/// value doesn't convert to the field's type. A field typed `Option<T>`
+/// gets `None` for a column that's present but `NULL`; a bare `T` field
+/// treats that same `NULL` as a conversion error instead.
+#[proc_macro_derive(FromRow)]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let ident = input.ident;
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => panic!("FromRow can only be derived for structs with named fields"),
+        },
+        _ => panic!("FromRow can only be derived for structs with named fields"),
+    };
+
+    let field_idents: Vec<_> = fields
+        .iter()
+        .map(|field| field.ident.clone().unwrap())
+        .collect();
+
+    let reads = field_idents.iter().map(|field| {
+        let name = field.to_string();
+
+        quote! {
+            let #field = tiberius::FromColumn::from_column(row, #name, #name)?;
+        }
+    });
+
+    let expanded = quote! {
+        impl tiberius::FromRow for #ident {
+            fn from_row(row: &tiberius::Row) -> tiberius::Result<Self> {
+                #(#reads)*
+
+                Ok(Self { #(#field_idents),* })
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,117 @@
+use crate::Row;
+
+/// Converts a [`Row`] into a concrete type, so result streams can yield `T`
+/// directly instead of forcing every caller through
+/// `row.get::<_, i32>(0)`.
+///
+/// Implemented here for tuples up to a reasonable arity, reading columns by
+/// position. Structs can implement it by hand, or via
+/// `#[derive(FromRow)]`, which reads columns by name.
+///
+/// ```ignore
+/// #[derive(tiberius::FromRow)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// let user: User = row_stream.into_typed::<User>().try_next().await?.unwrap();
+/// ```
+pub trait FromRow: Sized {
+    /// Builds `Self` out of a single row, failing if a column is missing or
+    /// a value cannot be converted to the expected type. A `NULL` column
+    /// is only an error for a field typed as a bare `T`; an `Option<T>`
+    /// field gets `None` instead — see [`FromColumn`].
+    fn from_row(row: &Row) -> crate::Result<Self>;
+}
+
+pub use tiberius_derive::FromRow;
+
+/// Reads a single column out of a [`Row`], by position or by name.
+///
+/// Exists so [`FromRow`] impls (the tuple impls below and
+/// `#[derive(FromRow)]`) can treat a genuinely `NULL` column and a field
+/// typed `Option<T>` the same way, while a column that's missing from the
+/// result set entirely still fails instead of silently becoming `None`.
+/// [`Row::try_get`] already tells the two apart — `Err` for missing,
+/// `Ok(None)` for `NULL` — this trait just decides what a field does with
+/// the `Ok(None)` case: a bare `T` rejects it, `Option<T>` keeps it.
+///
+/// [`Row::try_get`]: struct.Row.html#method.try_get
+pub trait FromColumn<'a>: Sized {
+    /// Reads the column at `idx` (a position for the tuple impls, a name
+    /// for `#[derive(FromRow)]`) out of `row`, using `name` only to build
+    /// an error message.
+    fn from_column<I: crate::QueryIdx>(row: &'a Row, idx: I, name: &str) -> crate::Result<Self>;
+}
+
+impl<'a, T> FromColumn<'a> for T
+where
+    T: crate::FromSql<'a>,
+{
+    fn from_column<I: crate::QueryIdx>(row: &'a Row, idx: I, name: &str) -> crate::Result<Self> {
+        row.try_get(idx)?
+            .ok_or_else(|| crate::Error::Conversion(format!("column `{}` is NULL", name).into()))
+    }
+}
+
+impl<'a, T> FromColumn<'a> for Option<T>
+where
+    T: crate::FromSql<'a>,
+{
+    fn from_column<I: crate::QueryIdx>(row: &'a Row, idx: I, _name: &str) -> crate::Result<Self> {
+        row.try_get(idx)
+    }
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($($idx:tt => $ty:ident),+ $(,)?) => {
+        impl<$($ty),+> FromRow for ($($ty,)+)
+        where
+            $($ty: for<'a> FromColumn<'a>,)+
+        {
+            fn from_row(row: &Row) -> crate::Result<Self> {
+                Ok(($(
+                    FromColumn::from_column(row, $idx, stringify!($idx))?,
+                )+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(0 => A);
+impl_from_row_for_tuple!(0 => A, 1 => B);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+impl_from_row_for_tuple!(0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `Row` is only constructible from a live result set, so these don't
+    // exercise actual column decoding; they pin down that every arity up
+    // to 8 is wired to `FromRow` and stays that way as the macro evolves.
+    fn assert_from_row<T: FromRow>() {}
+
+    #[test]
+    fn tuple_impls_cover_arities_one_through_eight() {
+        assert_from_row::<(i32,)>();
+        assert_from_row::<(i32, String)>();
+        assert_from_row::<(i32, String, i32)>();
+        assert_from_row::<(i32, String, i32, i32)>();
+        assert_from_row::<(i32, String, i32, i32, i32)>();
+        assert_from_row::<(i32, String, i32, i32, i32, i32)>();
+        assert_from_row::<(i32, String, i32, i32, i32, i32, i32)>();
+        assert_from_row::<(i32, String, i32, i32, i32, i32, i32, i32)>();
+    }
+
+    #[test]
+    fn tuple_impls_support_nullable_columns() {
+        assert_from_row::<(Option<i32>,)>();
+        assert_from_row::<(i32, Option<String>)>();
+    }
+}
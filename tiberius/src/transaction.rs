@@ -0,0 +1,304 @@
+use crate::{client::Connection, ExecuteResult, QueryResult};
+
+/// Isolation level for a [`Transaction`], set for its duration by
+/// [`Connection::transaction_with_isolation_level`].
+///
+/// See the [`SET TRANSACTION ISOLATION LEVEL`] docs for the semantics of
+/// each level.
+///
+/// [`Transaction`]: struct.Transaction.html
+/// [`Connection::transaction_with_isolation_level`]: struct.Connection.html#method.transaction_with_isolation_level
+/// [`SET TRANSACTION ISOLATION LEVEL`]: https://learn.microsoft.com/en-us/sql/t-sql/statements/set-transaction-isolation-level-transact-sql
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+    Snapshot,
+}
+
+impl IsolationLevel {
+    fn as_tsql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+            IsolationLevel::Snapshot => "SNAPSHOT",
+        }
+    }
+}
+
+impl Connection {
+    /// Starts a transaction with the connection's default isolation level
+    /// (`READ COMMITTED`). See [`transaction_with_isolation_level`] to pick
+    /// a different one.
+    ///
+    /// [`transaction_with_isolation_level`]: #method.transaction_with_isolation_level
+    pub async fn transaction(&mut self) -> crate::Result<Transaction<'_>> {
+        self.transaction_with_isolation_level(IsolationLevel::ReadCommitted)
+            .await
+    }
+
+    /// Starts a transaction, setting the isolation level for its duration,
+    /// and issuing `BEGIN TRANSACTION`. Wrap the unit of work in the
+    /// returned [`Transaction`] and call [`Transaction::commit`] on
+    /// success; a `?` that bails out before that rolls it back
+    /// automatically when the handle is dropped.
+    ///
+    /// `SET TRANSACTION ISOLATION LEVEL` is session-scoped in T-SQL rather
+    /// than transaction-scoped, so [`Transaction`] restores
+    /// `READ COMMITTED` — the connection's own default — once the
+    /// transaction ends, leaving no trace of a non-default level on the
+    /// connection for whatever runs next.
+    ///
+    /// [`Transaction`]: struct.Transaction.html
+    /// [`Transaction::commit`]: struct.Transaction.html#method.commit
+    pub async fn transaction_with_isolation_level(
+        &mut self,
+        level: IsolationLevel,
+    ) -> crate::Result<Transaction<'_>> {
+        self.execute(
+            format!("SET TRANSACTION ISOLATION LEVEL {}", level.as_tsql()),
+            &[],
+        )
+        .await?
+        .total()
+        .await?;
+
+        self.execute("BEGIN TRANSACTION", &[]).await?.total().await?;
+
+        Ok(Transaction {
+            connection: self,
+            savepoints: 0,
+            level,
+            done: false,
+        })
+    }
+}
+
+/// A unit of work guarded by `BEGIN TRANSACTION` / `COMMIT TRANSACTION` /
+/// `ROLLBACK TRANSACTION`, returned from [`Connection::transaction`].
+///
+/// Dropping a `Transaction` without calling [`commit`] or [`rollback`]
+/// marks the connection with a pending `ROLLBACK TRANSACTION`, following
+/// the "wrap the whole unit of work in a transaction and roll back on
+/// failure" pattern: a `?` that bails out of the caller's function rolls
+/// the transaction back instead of leaving it open. The rollback itself is
+/// sent lazily before the connection's next statement, since `Drop` has
+/// no way to await it directly — the same undeterministic-flush trade-off
+/// already documented on [`QueryResult`] and [`ExecuteResult`] for an
+/// un-drained stream.
+///
+/// That laziness comes with a sharp edge: nothing sends the pending
+/// rollback until another statement runs on the connection. If a
+/// `Transaction` is dropped and the connection is then handed back to a
+/// pool without anyone ever issuing another statement on it, the
+/// transaction is left open on the wire. **Pools that reuse connections
+/// must flush the pending rollback** — e.g. by issuing a no-op statement
+/// as part of checking a connection back in — **before handing it to the
+/// next caller**; this type has no way to do that flush itself, since
+/// nothing here owns a point in time after the last statement but before
+/// reuse.
+///
+/// A statement issued on a `Transaction` borrows it mutably for the
+/// lifetime of the returned stream, so the borrow checker — not a runtime
+/// flag — is what stops a second statement from starting before the first
+/// one is fully polled; see [`query`] and [`execute`].
+///
+/// [`commit`]: #method.commit
+/// [`rollback`]: #method.rollback
+/// [`query`]: #method.query
+/// [`execute`]: #method.execute
+/// [`QueryResult`]: struct.QueryResult.html
+/// [`ExecuteResult`]: struct.ExecuteResult.html
+pub struct Transaction<'a> {
+    connection: &'a mut Connection,
+    savepoints: u32,
+    level: IsolationLevel,
+    done: bool,
+}
+
+impl<'a> Transaction<'a> {
+    /// Executes a query, returning a stream of the resulting rows.
+    ///
+    /// Borrows `self` mutably for the lifetime of the returned stream, so
+    /// it isn't possible to start another statement on this transaction
+    /// before this one's `QueryResult` is dropped.
+    ///
+    /// [`QueryResult`]: struct.QueryResult.html
+    pub async fn query(
+        &mut self,
+        sql: impl Into<String>,
+        params: &[&dyn crate::ToSql],
+    ) -> crate::Result<QueryResult<'_>> {
+        self.connection.query(sql, params).await
+    }
+
+    /// Executes a statement, returning a stream of affected row counts. See
+    /// [`query`] for the borrow that keeps statements from overlapping.
+    ///
+    /// [`query`]: #method.query
+    pub async fn execute(
+        &mut self,
+        sql: impl Into<String>,
+        params: &[&dyn crate::ToSql],
+    ) -> crate::Result<ExecuteResult<'_>> {
+        self.connection.execute(sql, params).await
+    }
+
+    /// Opens a nested scope via `SAVE TRANSACTION`. Rolling back the
+    /// returned [`Savepoint`] — explicitly, or by dropping it without
+    /// [`release`] — only undoes work done since it was taken, leaving the
+    /// rest of this transaction intact.
+    ///
+    /// [`Savepoint`]: struct.Savepoint.html
+    /// [`release`]: struct.Savepoint.html#method.release
+    pub async fn savepoint(&mut self) -> crate::Result<Savepoint<'_, 'a>> {
+        self.savepoints += 1;
+        let name = format!("tiberius_sp_{}", self.savepoints);
+
+        self.connection
+            .execute(format!("SAVE TRANSACTION {}", name), &[])
+            .await?
+            .total()
+            .await?;
+
+        Ok(Savepoint {
+            transaction: self,
+            name,
+            done: false,
+        })
+    }
+
+    /// Commits the transaction, making its changes permanent.
+    pub async fn commit(mut self) -> crate::Result<()> {
+        self.connection
+            .execute("COMMIT TRANSACTION", &[])
+            .await?
+            .total()
+            .await?;
+
+        self.done = true;
+        self.restore_isolation_level().await?;
+
+        Ok(())
+    }
+
+    /// Rolls back the transaction, undoing everything done since `BEGIN
+    /// TRANSACTION`. Equivalent to just dropping the `Transaction`, except
+    /// the rollback is awaited here instead of sent lazily.
+    pub async fn rollback(mut self) -> crate::Result<()> {
+        self.connection
+            .execute("ROLLBACK TRANSACTION", &[])
+            .await?
+            .total()
+            .await?;
+
+        self.done = true;
+        self.restore_isolation_level().await?;
+
+        Ok(())
+    }
+
+    /// Resets the session back to `READ COMMITTED` if
+    /// [`Connection::transaction_with_isolation_level`] changed it for this
+    /// transaction, so the level doesn't leak into whatever runs next on
+    /// the connection.
+    ///
+    /// [`Connection::transaction_with_isolation_level`]: struct.Connection.html#method.transaction_with_isolation_level
+    async fn restore_isolation_level(&mut self) -> crate::Result<()> {
+        if self.level == IsolationLevel::ReadCommitted {
+            return Ok(());
+        }
+
+        self.connection
+            .execute(
+                format!(
+                    "SET TRANSACTION ISOLATION LEVEL {}",
+                    IsolationLevel::ReadCommitted.as_tsql()
+                ),
+                &[],
+            )
+            .await?
+            .total()
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<'a> Drop for Transaction<'a> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.connection.mark_pending_rollback();
+
+            if self.level != IsolationLevel::ReadCommitted {
+                self.connection
+                    .mark_pending_isolation_level(IsolationLevel::ReadCommitted);
+            }
+        }
+    }
+}
+
+/// A nested scope within a [`Transaction`], opened by
+/// [`Transaction::savepoint`]. Dropping it without [`release`] rolls back
+/// only the work done since the savepoint was taken.
+///
+/// [`Transaction`]: struct.Transaction.html
+/// [`Transaction::savepoint`]: struct.Transaction.html#method.savepoint
+/// [`release`]: #method.release
+pub struct Savepoint<'a, 'b> {
+    transaction: &'a mut Transaction<'b>,
+    name: String,
+    done: bool,
+}
+
+impl<'a, 'b> Savepoint<'a, 'b> {
+    /// Keeps the work done since this savepoint, folding it back into the
+    /// enclosing transaction.
+    pub fn release(mut self) {
+        self.done = true;
+    }
+
+    /// Rolls back to this savepoint, undoing everything done since it was
+    /// taken, without touching the rest of the enclosing transaction.
+    pub async fn rollback(mut self) -> crate::Result<()> {
+        self.transaction
+            .connection
+            .execute(format!("ROLLBACK TRANSACTION {}", self.name), &[])
+            .await?
+            .total()
+            .await?;
+
+        self.done = true;
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Drop for Savepoint<'a, 'b> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.transaction.connection.mark_pending_rollback_to(&self.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isolation_level_as_tsql() {
+        assert_eq!(
+            "READ UNCOMMITTED",
+            IsolationLevel::ReadUncommitted.as_tsql()
+        );
+        assert_eq!("READ COMMITTED", IsolationLevel::ReadCommitted.as_tsql());
+        assert_eq!("REPEATABLE READ", IsolationLevel::RepeatableRead.as_tsql());
+        assert_eq!("SERIALIZABLE", IsolationLevel::Serializable.as_tsql());
+        assert_eq!("SNAPSHOT", IsolationLevel::Snapshot.as_tsql());
+    }
+}